@@ -1,11 +1,11 @@
 use std::time::Duration;
 
-use chrono::{DateTime, Local, TimeZone, Utc};
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, SecondsFormat, TimeZone, Utc};
 use prost::Message;
 use sea_orm::{QueryResult, TryGetError, sea_query};
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Message)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Message)]
 pub struct Timestamp {
     #[prost(int64, tag = 1)]
     pub seconds: i64,
@@ -13,6 +13,84 @@ pub struct Timestamp {
     pub nanoseconds: i32,
 }
 
+impl Serialize for Timestamp {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        if serializer.is_human_readable() {
+            let datetime: DateTime<Utc> = (*self).into();
+            serializer.serialize_str(&datetime.to_rfc3339_opts(SecondsFormat::Nanos, true))
+        } else {
+            use serde::ser::SerializeStruct;
+            let mut state = serializer.serialize_struct("Timestamp", 2)?;
+            state.serialize_field("seconds", &self.seconds)?;
+            state.serialize_field("nanoseconds", &self.nanoseconds)?;
+            state.end()
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Timestamp {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        if deserializer.is_human_readable() {
+            struct HumanReadableVisitor;
+            impl serde::de::Visitor<'_> for HumanReadableVisitor {
+                type Value = Timestamp;
+
+                fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                    f.write_str("an RFC 3339 timestamp string or a unix timestamp number")
+                }
+
+                fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+                where
+                    E: serde::de::Error,
+                {
+                    DateTime::parse_from_rfc3339(v)
+                        .map(|v| v.with_timezone(&Utc).into())
+                        .map_err(E::custom)
+                }
+
+                fn visit_f64<E>(self, v: f64) -> Result<Self::Value, E>
+                where
+                    E: serde::de::Error,
+                {
+                    Ok(v.into())
+                }
+
+                fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
+                where
+                    E: serde::de::Error,
+                {
+                    Ok((v as f64).into())
+                }
+
+                fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+                where
+                    E: serde::de::Error,
+                {
+                    Ok((v as f64).into())
+                }
+            }
+            deserializer.deserialize_any(HumanReadableVisitor)
+        } else {
+            #[derive(Deserialize)]
+            struct Raw {
+                seconds: i64,
+                nanoseconds: i32,
+            }
+            let raw = Raw::deserialize(deserializer)?;
+            Ok(Timestamp {
+                seconds: raw.seconds,
+                nanoseconds: raw.nanoseconds,
+            })
+        }
+    }
+}
+
 impl Timestamp {
     pub fn new() -> Self {
         let dt = Utc::now();
@@ -28,6 +106,26 @@ impl Timestamp {
     pub fn local_datetime(&self) -> DateTime<Local> {
         self.datetime().into()
     }
+
+    /// Normalizes `nanoseconds` into `0..1_000_000_000`, carrying any
+    /// overflow (or a negative value) into `seconds`, per the
+    /// `google.protobuf.Timestamp` invariant.
+    pub fn normalize(&self) -> Self {
+        let mut seconds = self.seconds;
+        let mut nanoseconds = self.nanoseconds;
+        if !(0..1_000_000_000).contains(&nanoseconds) {
+            seconds += (nanoseconds / 1_000_000_000) as i64;
+            nanoseconds %= 1_000_000_000;
+        }
+        if nanoseconds < 0 {
+            seconds -= 1;
+            nanoseconds += 1_000_000_000;
+        }
+        Timestamp {
+            seconds,
+            nanoseconds,
+        }
+    }
 }
 
 impl From<Duration> for Timestamp {
@@ -89,6 +187,47 @@ impl From<Timestamp> for i64 {
     }
 }
 
+impl From<prost_types::Timestamp> for Timestamp {
+    fn from(v: prost_types::Timestamp) -> Self {
+        Timestamp {
+            seconds: v.seconds,
+            nanoseconds: v.nanos,
+        }
+        .normalize()
+    }
+}
+
+impl From<Timestamp> for prost_types::Timestamp {
+    fn from(v: Timestamp) -> Self {
+        let v = v.normalize();
+        prost_types::Timestamp {
+            seconds: v.seconds,
+            nanos: v.nanoseconds,
+        }
+    }
+}
+
+/// Parses the assorted text shapes SQLite's own date/time functions emit
+/// (`datetime()`, `date()`, a raw RFC 3339 string, or a Julian day number)
+/// into a UTC `Timestamp`, returning `None` if none of them match.
+fn parse_sqlite_text(s: &str) -> Option<Timestamp> {
+    if let Ok(v) = DateTime::parse_from_rfc3339(s) {
+        return Some(v.with_timezone(&Utc).into());
+    }
+    for fmt in ["%Y-%m-%d %H:%M:%S%.f", "%Y-%m-%dT%H:%M:%S%.f"] {
+        if let Ok(v) = NaiveDateTime::parse_from_str(s, fmt) {
+            return Some(v.and_utc().into());
+        }
+    }
+    if let Ok(v) = NaiveDate::parse_from_str(s, "%Y-%m-%d") {
+        return Some(v.and_hms_opt(0, 0, 0).unwrap().and_utc().into());
+    }
+    if let Ok(jd) = s.parse::<f64>() {
+        return Some(((jd - 2440587.5) * 86400.0).into());
+    }
+    None
+}
+
 impl sea_query::ValueType for Timestamp {
     fn try_from(v: sea_query::Value) -> Result<Self, sea_query::ValueTypeErr> {
         match v {
@@ -98,6 +237,9 @@ impl sea_query::ValueType for Timestamp {
             // sea_query::Value::ChronoDateTime(Some(v)) => (*v).into(),
             sea_query::Value::Double(Some(v)) => Ok(v.into()),
             sea_query::Value::Int(Some(v)) => Ok((v as i64).into()),
+            sea_query::Value::String(Some(v)) => {
+                parse_sqlite_text(&v).ok_or(sea_query::ValueTypeErr)
+            }
             _ => Ok(Timestamp::default()),
         }
     }
@@ -110,6 +252,8 @@ impl sea_query::ValueType for Timestamp {
             <f64 as sea_query::ValueType>::array_type()
         } else if cfg!(feature = "sqlite_int") {
             <i64 as sea_query::ValueType>::array_type()
+        } else if cfg!(feature = "sqlite_text") {
+            <String as sea_query::ValueType>::array_type()
         } else {
             <DateTime<Utc> as sea_query::ValueType>::array_type()
         }
@@ -120,6 +264,8 @@ impl sea_query::ValueType for Timestamp {
             <f64 as sea_query::ValueType>::column_type()
         } else if cfg!(feature = "sqlite_int") {
             <i64 as sea_query::ValueType>::column_type()
+        } else if cfg!(feature = "sqlite_text") {
+            <String as sea_query::ValueType>::column_type()
         } else {
             <DateTime<Utc> as sea_query::ValueType>::column_type()
         }
@@ -132,6 +278,11 @@ impl From<Timestamp> for sea_query::Value {
             sea_query::Value::Double(Some(v.into()))
         } else if cfg!(feature = "sqlite_int") {
             sea_query::Value::BigInt(Some(v.into()))
+        } else if cfg!(feature = "sqlite_text") {
+            let datetime: DateTime<Utc> = v.into();
+            sea_query::Value::String(Some(Box::new(
+                datetime.to_rfc3339_opts(SecondsFormat::Nanos, true),
+            )))
         } else {
             sea_query::Value::ChronoDateTimeUtc(Some(Box::new(v.into())))
         }
@@ -146,6 +297,13 @@ impl sea_orm::TryGetable for Timestamp {
         } else if cfg!(feature = "sqlite_int") {
             let data: i64 = <i64 as sea_orm::TryGetable>::try_get_by(res, index)?;
             Ok(data.into())
+        } else if cfg!(feature = "sqlite_text") {
+            let data: String = <String as sea_orm::TryGetable>::try_get_by(res, index)?;
+            parse_sqlite_text(&data).ok_or_else(|| {
+                TryGetError::DbErr(sea_orm::DbErr::Type(format!(
+                    "could not parse Timestamp from {data:?}"
+                )))
+            })
         } else {
             let data: DateTime<Utc> =
                 <DateTime<Utc> as sea_orm::TryGetable>::try_get_by(res, index)?;
@@ -159,6 +317,8 @@ impl sea_orm::sea_query::Nullable for Timestamp {
             sea_orm::Value::Double(None)
         } else if cfg!(feature = "sqlite_int") {
             sea_orm::Value::BigInt(None)
+        } else if cfg!(feature = "sqlite_text") {
+            sea_orm::Value::String(None)
         } else {
             sea_orm::Value::ChronoDateTimeUtc(None)
         }
@@ -170,3 +330,271 @@ impl sea_orm::TryFromU64 for Timestamp {
         Err(sea_orm::DbErr::ConvertFromU64("Timestamps not supported"))
     }
 }
+
+impl sea_query::ValueType for Vec<Timestamp> {
+    fn try_from(v: sea_query::Value) -> Result<Self, sea_query::ValueTypeErr> {
+        match v {
+            sea_query::Value::Array(_, Some(values)) => values
+                .into_iter()
+                .map(<Timestamp as sea_query::ValueType>::try_from)
+                .collect(),
+            _ => Err(sea_query::ValueTypeErr),
+        }
+    }
+
+    fn type_name() -> String {
+        "Vec<Timestamp>".to_string()
+    }
+
+    fn array_type() -> sea_query::ArrayType {
+        <Timestamp as sea_query::ValueType>::array_type()
+    }
+
+    fn column_type() -> sea_query::ColumnType {
+        sea_query::ColumnType::Array(Box::new(<Timestamp as sea_query::ValueType>::column_type()))
+    }
+}
+
+impl From<Vec<Timestamp>> for sea_query::Value {
+    fn from(v: Vec<Timestamp>) -> Self {
+        let array_type = <Timestamp as sea_query::ValueType>::array_type();
+        let values = v.into_iter().map(sea_query::Value::from).collect();
+        sea_query::Value::Array(array_type, Some(Box::new(values)))
+    }
+}
+
+impl sea_orm::TryGetable for Vec<Timestamp> {
+    fn try_get_by<I: sea_orm::ColIdx>(res: &QueryResult, index: I) -> Result<Self, TryGetError> {
+        if cfg!(feature = "sqlite_double") {
+            let data: Vec<f64> = <Vec<f64> as sea_orm::TryGetable>::try_get_by(res, index)?;
+            Ok(data.into_iter().map(Timestamp::from).collect())
+        } else if cfg!(feature = "sqlite_int") {
+            let data: Vec<i64> = <Vec<i64> as sea_orm::TryGetable>::try_get_by(res, index)?;
+            Ok(data.into_iter().map(Timestamp::from).collect())
+        } else if cfg!(feature = "sqlite_text") {
+            let data: Vec<String> = <Vec<String> as sea_orm::TryGetable>::try_get_by(res, index)?;
+            data.iter()
+                .map(|s| parse_sqlite_text(s))
+                .collect::<Option<Vec<_>>>()
+                .ok_or_else(|| {
+                    TryGetError::DbErr(sea_orm::DbErr::Type(
+                        "could not parse Timestamp array".to_string(),
+                    ))
+                })
+        } else {
+            let data: Vec<DateTime<Utc>> =
+                <Vec<DateTime<Utc>> as sea_orm::TryGetable>::try_get_by(res, index)?;
+            Ok(data.into_iter().map(Timestamp::from).collect())
+        }
+    }
+}
+
+impl sea_orm::sea_query::Nullable for Vec<Timestamp> {
+    fn null() -> sea_orm::Value {
+        sea_query::Value::Array(<Timestamp as sea_query::ValueType>::array_type(), None)
+    }
+}
+
+#[cfg(feature = "time")]
+impl From<time::OffsetDateTime> for Timestamp {
+    fn from(v: time::OffsetDateTime) -> Self {
+        Timestamp {
+            seconds: v.unix_timestamp(),
+            nanoseconds: v.nanosecond() as i32,
+        }
+    }
+}
+
+#[cfg(feature = "time")]
+impl From<Timestamp> for time::OffsetDateTime {
+    fn from(v: Timestamp) -> Self {
+        time::OffsetDateTime::from_unix_timestamp(v.seconds)
+            .map(|dt| dt + time::Duration::nanoseconds(v.nanoseconds as i64))
+            .unwrap_or(time::OffsetDateTime::UNIX_EPOCH)
+    }
+}
+
+#[cfg(feature = "time")]
+impl From<time::PrimitiveDateTime> for Timestamp {
+    fn from(v: time::PrimitiveDateTime) -> Self {
+        v.assume_utc().into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_sqlite_text_rfc3339() {
+        let text = "2024-01-02T03:04:05.123456789+00:00";
+        let expected: DateTime<Utc> = DateTime::parse_from_rfc3339(text).unwrap().into();
+        assert_eq!(parse_sqlite_text(text), Some(expected.into()));
+    }
+
+    #[test]
+    fn parse_sqlite_text_space_separated_datetime() {
+        let text = "2024-01-02 03:04:05.5";
+        let expected = NaiveDateTime::parse_from_str(text, "%Y-%m-%d %H:%M:%S%.f")
+            .unwrap()
+            .and_utc();
+        assert_eq!(parse_sqlite_text(text), Some(expected.into()));
+    }
+
+    #[test]
+    fn parse_sqlite_text_t_separated_datetime() {
+        let text = "2024-01-02T03:04:05.5";
+        let expected = NaiveDateTime::parse_from_str(text, "%Y-%m-%dT%H:%M:%S%.f")
+            .unwrap()
+            .and_utc();
+        assert_eq!(parse_sqlite_text(text), Some(expected.into()));
+    }
+
+    #[test]
+    fn parse_sqlite_text_date_only() {
+        let text = "2024-01-02";
+        let expected = NaiveDate::parse_from_str(text, "%Y-%m-%d")
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap()
+            .and_utc();
+        assert_eq!(parse_sqlite_text(text), Some(expected.into()));
+    }
+
+    #[test]
+    fn parse_sqlite_text_julian_day() {
+        assert_eq!(
+            parse_sqlite_text("2440587.5"),
+            Some(Timestamp {
+                seconds: 0,
+                nanoseconds: 0,
+            })
+        );
+    }
+
+    #[test]
+    fn parse_sqlite_text_unparseable() {
+        assert_eq!(parse_sqlite_text("not a timestamp"), None);
+    }
+
+    #[test]
+    fn normalize_positive_overflow() {
+        let ts = Timestamp {
+            seconds: 10,
+            nanoseconds: 1_500_000_000,
+        };
+        assert_eq!(
+            ts.normalize(),
+            Timestamp {
+                seconds: 11,
+                nanoseconds: 500_000_000,
+            }
+        );
+    }
+
+    #[test]
+    fn normalize_single_second_negative_borrow() {
+        let ts = Timestamp {
+            seconds: 10,
+            nanoseconds: -500_000_000,
+        };
+        assert_eq!(
+            ts.normalize(),
+            Timestamp {
+                seconds: 9,
+                nanoseconds: 500_000_000,
+            }
+        );
+    }
+
+    #[test]
+    fn normalize_multi_second_negative_overflow() {
+        let ts = Timestamp {
+            seconds: 10,
+            nanoseconds: -1_500_000_000,
+        };
+        assert_eq!(
+            ts.normalize(),
+            Timestamp {
+                seconds: 8,
+                nanoseconds: 500_000_000,
+            }
+        );
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn offset_date_time_out_of_range_clamps_to_epoch() {
+        let ts = Timestamp {
+            seconds: i64::MAX,
+            nanoseconds: 500_000_000,
+        };
+        let dt: time::OffsetDateTime = ts.into();
+        assert_eq!(dt, time::OffsetDateTime::UNIX_EPOCH);
+    }
+
+    #[test]
+    fn json_round_trip_is_human_readable_rfc3339() {
+        let ts = Timestamp {
+            seconds: 1_700_000_000,
+            nanoseconds: 123_456_789,
+        };
+        let json = serde_json::to_string(&ts).unwrap();
+        let expected: DateTime<Utc> = ts.into();
+        assert_eq!(
+            json,
+            format!("\"{}\"", expected.to_rfc3339_opts(SecondsFormat::Nanos, true))
+        );
+        let round_tripped: Timestamp = serde_json::from_str(&json).unwrap();
+        assert_eq!(round_tripped, ts);
+    }
+
+    #[test]
+    fn bincode_round_trip_keeps_compact_two_field_form() {
+        let ts = Timestamp {
+            seconds: 1_700_000_000,
+            nanoseconds: 123_456_789,
+        };
+        let bytes = bincode::serialize(&ts).unwrap();
+        assert_eq!(
+            bytes,
+            bincode::serialize(&(ts.seconds, ts.nanoseconds)).unwrap()
+        );
+        let round_tripped: Timestamp = bincode::deserialize(&bytes).unwrap();
+        assert_eq!(round_tripped, ts);
+    }
+
+    #[test]
+    fn vec_timestamp_value_round_trips_through_array() {
+        let timestamps = vec![
+            Timestamp {
+                seconds: 1,
+                nanoseconds: 0,
+            },
+            Timestamp {
+                seconds: 2,
+                nanoseconds: 500_000_000,
+            },
+        ];
+        let value: sea_query::Value = timestamps.clone().into();
+        let round_tripped = <Vec<Timestamp> as sea_query::ValueType>::try_from(value).unwrap();
+        assert_eq!(round_tripped, timestamps);
+    }
+
+    #[cfg(feature = "sqlite_text")]
+    #[test]
+    fn sqlite_text_value_round_trips_through_parse_sqlite_text() {
+        let ts = Timestamp {
+            seconds: 1_700_000_000,
+            nanoseconds: 123_456_789,
+        };
+        let value: sea_query::Value = ts.into();
+        let text = match &value {
+            sea_query::Value::String(Some(s)) => (**s).clone(),
+            _ => panic!("expected sqlite_text encoding to produce a String value"),
+        };
+        assert_eq!(parse_sqlite_text(&text), Some(ts));
+        let round_tripped = <Timestamp as sea_query::ValueType>::try_from(value).unwrap();
+        assert_eq!(round_tripped, ts);
+    }
+}